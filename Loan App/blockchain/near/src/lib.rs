@@ -1,8 +1,16 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LookupMap, UnorderedMap};
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, near_bindgen, AccountId, Balance, PanicOnDefault, Timestamp};
-use std::collections::HashMap;
+use near_sdk::serde_json::json;
+use near_sdk::{
+    assert_one_yocto, env, is_promise_success, near_bindgen, AccountId, Balance, Gas,
+    PanicOnDefault, Promise, Timestamp,
+};
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_ON_CLAIM: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_ON_RESCUE_FT: Gas = Gas(5_000_000_000_000);
 
 // ============================================================================
 // DOMAIN MODELS
@@ -38,6 +46,7 @@ pub struct LoanToken {
     pub created_at: Timestamp,
     pub updated_at: Timestamp,
     pub originator: AccountId,
+    pub lot_size_bps: Option<u64>, // Transfers must be multiples of this, if set
 }
 
 /// Transfer event record for audit trail
@@ -53,6 +62,52 @@ pub struct TransferEvent {
     pub block_height: u64,
 }
 
+/// Cumulative funds distributed for a token, by distribution type
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DistributionBreakdown {
+    pub interest_total: Balance,
+    pub recovery_total: Balance,
+    pub bonus_total: Balance,
+    pub principal_total: Balance,
+}
+
+/// Consolidated per-account view for profile pages
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountSummary {
+    pub holdings_count: u64,
+    pub portfolio_value: Balance, // in yoctoNEAR
+    pub total_claimable: Balance,
+}
+
+// ============================================================================
+// LEGACY STATE (v1.0.0)
+// ============================================================================
+
+/// Loan token layout before lot sizes were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LoanTokenV1 {
+    pub token_id: String,
+    pub off_chain_loan_id: String,
+    pub total_value: Balance,
+    pub owners: Vec<FractionalOwnership>,
+    pub lifecycle_status: LifecycleStatus,
+    pub created_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub originator: AccountId,
+}
+
+/// Contract state layout of version 1.0.0, read by `migrate`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LoanTradingContractV1 {
+    pub loan_tokens: UnorderedMap<String, LoanTokenV1>,
+    pub transfer_history: UnorderedMap<String, Vec<TransferEvent>>,
+    pub authorized_originators: LookupMap<AccountId, bool>,
+    pub admin: AccountId,
+    pub version: String,
+}
+
 // ============================================================================
 // SMART CONTRACT
 // ============================================================================
@@ -69,6 +124,27 @@ pub struct LoanTradingContract {
     /// Authorized originators (can register loans)
     pub authorized_originators: LookupMap<AccountId, bool>,
     
+    /// Distributed funds awaiting withdrawal, per account
+    pub claimable: LookupMap<AccountId, Balance>,
+    
+    /// Distribution totals by type, per token
+    pub distribution_totals: LookupMap<String, DistributionBreakdown>,
+    
+    /// Most recent acquisition time, per (token_id, account)
+    pub acquired_at: LookupMap<(String, AccountId), Timestamp>,
+    
+    /// Owner positions under a legal hold, per (token_id, account)
+    pub frozen_positions: LookupMap<(String, AccountId), bool>,
+    
+    /// Time of the last lifecycle status change, per token_id
+    pub status_changed_at: LookupMap<String, Timestamp>,
+    
+    /// Minimum hold period after an acquisition before reselling (0 = disabled)
+    pub post_purchase_lock_ns: u64,
+    
+    /// Minimum time between lifecycle status changes on a token (0 = disabled)
+    pub lifecycle_change_cooldown_ns: u64,
+    
     /// Admin account
     pub admin: AccountId,
     
@@ -91,8 +167,64 @@ impl LoanTradingContract {
             loan_tokens: UnorderedMap::new(b"l"),
             transfer_history: UnorderedMap::new(b"t"),
             authorized_originators: LookupMap::new(b"o"),
+            claimable: LookupMap::new(b"c"),
+            distribution_totals: LookupMap::new(b"d"),
+            acquired_at: LookupMap::new(b"a"),
+            frozen_positions: LookupMap::new(b"f"),
+            status_changed_at: LookupMap::new(b"s"),
+            post_purchase_lock_ns: 0,
+            lifecycle_change_cooldown_ns: 0,
             admin,
-            version: "1.0.0".to_string(),
+            version: "1.1.0".to_string(),
+        }
+    }
+
+    /// Migrate version 1.0.0 state to the current layout (contract account only).
+    /// Rewrites every stored loan token, so gas grows with the number of tokens.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let mut old: LoanTradingContractV1 = env::state_read().expect("No contract state to migrate");
+        assert_eq!(old.version, "1.0.0", "Migration expects version 1.0.0 state");
+        
+        let old_tokens = old.loan_tokens.to_vec();
+        old.loan_tokens.clear();
+        
+        let mut loan_tokens = UnorderedMap::new(b"l");
+        for (token_id, token) in old_tokens {
+            loan_tokens.insert(&token_id, &LoanToken {
+                token_id: token.token_id,
+                off_chain_loan_id: token.off_chain_loan_id,
+                total_value: token.total_value,
+                owners: token.owners,
+                lifecycle_status: token.lifecycle_status,
+                created_at: token.created_at,
+                updated_at: token.updated_at,
+                originator: token.originator,
+                lot_size_bps: None,
+            });
+        }
+        
+        env::log_str(&format!(
+            "EVENT:CONTRACT_MIGRATED {{\"from_version\": \"{}\", \"to_version\": \"1.1.0\", \"tokens\": {}, \"timestamp\": {}}}",
+            old.version,
+            loan_tokens.len(),
+            env::block_timestamp()
+        ));
+        
+        Self {
+            loan_tokens,
+            transfer_history: old.transfer_history,
+            authorized_originators: old.authorized_originators,
+            claimable: LookupMap::new(b"c"),
+            distribution_totals: LookupMap::new(b"d"),
+            acquired_at: LookupMap::new(b"a"),
+            frozen_positions: LookupMap::new(b"f"),
+            status_changed_at: LookupMap::new(b"s"),
+            post_purchase_lock_ns: 0,
+            lifecycle_change_cooldown_ns: 0,
+            admin: old.admin,
+            version: "1.1.0".to_string(),
         }
     }
 
@@ -126,6 +258,115 @@ impl LoanTradingContract {
         ));
     }
 
+    /// Set the post-purchase hold period in nanoseconds (0 disables it).
+    /// An account that receives any fraction of a token, priced or not, cannot
+    /// transfer from that token until the lock elapses. For simplicity the lock
+    /// covers the whole position from the most recent acquisition, not only
+    /// the newly acquired fraction.
+    pub fn set_post_purchase_lock(&mut self, lock_ns: u64) {
+        self.assert_admin();
+        self.post_purchase_lock_ns = lock_ns;
+        
+        env::log_str(&format!(
+            "EVENT:POST_PURCHASE_LOCK_UPDATED {{\"lock_ns\": {}, \"by\": \"{}\", \"timestamp\": {}}}",
+            lock_ns,
+            env::predecessor_account_id(),
+            env::block_timestamp()
+        ));
+    }
+
+    /// Set the lifecycle status change cooldown in nanoseconds (0 disables it).
+    /// Measured from the token's last status change; transfers do not restart it.
+    pub fn set_lifecycle_change_cooldown(&mut self, cooldown_ns: u64) {
+        self.assert_admin();
+        self.lifecycle_change_cooldown_ns = cooldown_ns;
+        
+        env::log_str(&format!(
+            "EVENT:LIFECYCLE_COOLDOWN_UPDATED {{\"cooldown_ns\": {}, \"by\": \"{}\", \"timestamp\": {}}}",
+            cooldown_ns,
+            env::predecessor_account_id(),
+            env::block_timestamp()
+        ));
+    }
+
+    /// Freeze one owner's position in a token, leaving other owners free to trade
+    pub fn freeze_owner_position(&mut self, token_id: String, owner: AccountId) {
+        self.assert_admin();
+        assert!(self.loan_tokens.get(&token_id).is_some(), "Loan token not found");
+        self.frozen_positions.insert(&(token_id.clone(), owner.clone()), &true);
+        
+        env::log_str(&format!(
+            "EVENT:OWNER_POSITION_FROZEN {{\"token_id\": \"{}\", \"owner\": \"{}\", \"by\": \"{}\", \"timestamp\": {}}}",
+            token_id,
+            owner,
+            env::predecessor_account_id(),
+            env::block_timestamp()
+        ));
+    }
+
+    /// Lift a freeze on one owner's position
+    pub fn unfreeze_owner_position(&mut self, token_id: String, owner: AccountId) {
+        self.assert_admin();
+        self.frozen_positions.remove(&(token_id.clone(), owner.clone()));
+        
+        env::log_str(&format!(
+            "EVENT:OWNER_POSITION_UNFROZEN {{\"token_id\": \"{}\", \"owner\": \"{}\", \"by\": \"{}\", \"timestamp\": {}}}",
+            token_id,
+            owner,
+            env::predecessor_account_id(),
+            env::block_timestamp()
+        ));
+    }
+
+    /// Return fungible tokens sent to the contract by mistake (admin only, 1 yoctoNEAR).
+    /// The contract holds no fungible-token liabilities, so any FT balance is unbacked.
+    #[payable]
+    pub fn rescue_ft(&mut self, token_contract: AccountId, to: AccountId, amount: U128) -> Promise {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(amount.0 > 0, "Amount must be positive");
+        
+        Promise::new(token_contract.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({ "receiver_id": to, "amount": amount })
+                    .to_string()
+                    .into_bytes(),
+                1,
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_ON_RESCUE_FT)
+                    .on_rescue_ft(token_contract, to, amount, env::predecessor_account_id()),
+            )
+    }
+
+    /// Log the outcome of a rescue_ft transfer
+    #[private]
+    pub fn on_rescue_ft(
+        &mut self,
+        token_contract: AccountId,
+        to: AccountId,
+        amount: U128,
+        by: AccountId,
+    ) -> bool {
+        let succeeded = is_promise_success();
+        let event = if succeeded { "FT_RESCUED" } else { "FT_RESCUE_FAILED" };
+        
+        env::log_str(&format!(
+            "EVENT:{} {{\"token_contract\": \"{}\", \"to\": \"{}\", \"amount\": \"{}\", \"by\": \"{}\", \"timestamp\": {}}}",
+            event,
+            token_contract,
+            to,
+            amount.0,
+            by,
+            env::block_timestamp()
+        ));
+        
+        succeeded
+    }
+
     // ------------------------------------------------------------------------
     // LOAN TOKEN REGISTRATION
     // ------------------------------------------------------------------------
@@ -172,6 +413,7 @@ impl LoanTradingContract {
             created_at: now,
             updated_at: now,
             originator: originator.clone(),
+            lot_size_bps: None,
         };
         
         self.loan_tokens.insert(&token_id, &loan_token);
@@ -216,6 +458,12 @@ impl LoanTradingContract {
             "Loan must be active for transfers"
         );
         
+        // Verify sender's position is not frozen
+        assert!(
+            !self.is_owner_position_frozen(token_id.clone(), from.clone()),
+            "Owner position is frozen"
+        );
+        
         // Validate fraction
         assert!(fraction > 0 && fraction <= 10000, "Invalid fraction");
         
@@ -228,6 +476,22 @@ impl LoanTradingContract {
         let sender_fraction = loan_token.owners[sender_index].fraction;
         assert!(sender_fraction >= fraction, "Insufficient ownership fraction");
         
+        // Enforce lot size, except when liquidating the full position
+        if let Some(lot_size) = loan_token.lot_size_bps {
+            assert!(
+                fraction.is_multiple_of(lot_size) || fraction == sender_fraction,
+                "Fraction must be a multiple of the lot size"
+            );
+        }
+        
+        // Enforce minimum hold period since the sender's last acquisition
+        if let Some(acquired) = self.acquired_at.get(&(token_id.clone(), from.clone())) {
+            assert!(
+                env::block_timestamp() >= acquired.saturating_add(self.post_purchase_lock_ns),
+                "Position is locked after purchase"
+            );
+        }
+        
         // Update sender's fraction
         if sender_fraction == fraction {
             // Remove sender if selling entire fraction
@@ -253,6 +517,11 @@ impl LoanTradingContract {
         // Save updated token
         self.loan_tokens.insert(&token_id, &loan_token);
         
+        // Every receipt from another account starts the post-purchase lock
+        if to != from {
+            self.acquired_at.insert(&(token_id.clone(), to.clone()), &env::block_timestamp());
+        }
+        
         // Record transfer event
         let transfer_event = TransferEvent {
             token_id: token_id.clone(),
@@ -266,7 +535,7 @@ impl LoanTradingContract {
         
         let mut history = self.transfer_history
             .get(&token_id)
-            .unwrap_or_else(|| vec![]);
+            .unwrap_or_default();
         history.push(transfer_event);
         self.transfer_history.insert(&token_id, &history);
         
@@ -303,11 +572,20 @@ impl LoanTradingContract {
             "Only originator can update lifecycle status"
         );
         
+        // Enforce cooldown since the last status change
+        if let Some(changed_at) = self.status_changed_at.get(&token_id) {
+            assert!(
+                env::block_timestamp() >= changed_at.saturating_add(self.lifecycle_change_cooldown_ns),
+                "Lifecycle status changed too recently"
+            );
+        }
+        
         let old_status = loan_token.lifecycle_status.clone();
         loan_token.lifecycle_status = new_status.clone();
         loan_token.updated_at = env::block_timestamp();
         
         self.loan_tokens.insert(&token_id, &loan_token);
+        self.status_changed_at.insert(&token_id, &env::block_timestamp());
         
         env::log_str(&format!(
             "EVENT:LIFECYCLE_UPDATED {{\"token_id\": \"{}\", \"old_status\": \"{:?}\", \"new_status\": \"{:?}\", \"timestamp\": {}}}",
@@ -318,6 +596,122 @@ impl LoanTradingContract {
         ));
     }
 
+    /// Set or clear the transfer lot size in basis points (originator only)
+    pub fn set_lot_size(&mut self, token_id: String, lot_size_bps: Option<u64>) {
+        let caller = env::predecessor_account_id();
+        
+        let mut loan_token = self.loan_tokens
+            .get(&token_id)
+            .expect("Loan token not found");
+        
+        assert_eq!(
+            caller, loan_token.originator,
+            "Only originator can set lot size"
+        );
+        
+        if let Some(lot_size) = lot_size_bps {
+            assert!(lot_size > 0 && lot_size <= 10000, "Invalid lot size");
+        }
+        
+        loan_token.lot_size_bps = lot_size_bps;
+        loan_token.updated_at = env::block_timestamp();
+        
+        self.loan_tokens.insert(&token_id, &loan_token);
+        
+        env::log_str(&format!(
+            "EVENT:LOT_SIZE_UPDATED {{\"token_id\": \"{}\", \"lot_size_bps\": {}, \"timestamp\": {}}}",
+            token_id,
+            lot_size_bps.map_or("null".to_string(), |lot_size| lot_size.to_string()),
+            env::block_timestamp()
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // DISTRIBUTIONS
+    // ------------------------------------------------------------------------
+
+    /// Distribute the attached deposit as a bonus to current owners (admin or originator)
+    #[payable]
+    pub fn distribute_bonus(&mut self, token_id: String, reason: String) {
+        let caller = env::predecessor_account_id();
+        let amount = env::attached_deposit();
+        
+        let loan_token = self.loan_tokens
+            .get(&token_id)
+            .expect("Loan token not found");
+        
+        assert!(
+            caller == self.admin || caller == loan_token.originator,
+            "Only admin or originator can distribute a bonus"
+        );
+        assert!(amount > 0, "Attached deposit must be positive");
+        assert!(!reason.is_empty(), "Reason cannot be empty");
+        
+        for (owner, share) in self.compute_pro_rata(&loan_token, amount) {
+            let balance = self.claimable.get(&owner).unwrap_or(0);
+            self.claimable.insert(&owner, &(balance + share));
+        }
+        
+        let mut totals = self.get_distribution_breakdown(token_id.clone());
+        totals.bonus_total += amount;
+        self.distribution_totals.insert(&token_id, &totals);
+        
+        // Built with json! so the free-text reason is escaped
+        env::log_str(&format!(
+            "EVENT:BONUS_DISTRIBUTED {}",
+            json!({
+                "token_id": token_id,
+                "distribution_type": "bonus",
+                "amount": amount.to_string(),
+                "reason": reason,
+                "by": caller,
+                "timestamp": env::block_timestamp(),
+            })
+        ));
+    }
+
+    /// Withdraw all claimable funds for the caller
+    pub fn claim(&mut self) -> Promise {
+        let account = env::predecessor_account_id();
+        let amount = self.claimable.get(&account).unwrap_or(0);
+        assert!(amount > 0, "Nothing to claim");
+        
+        self.claimable.remove(&account);
+        
+        env::log_str(&format!(
+            "EVENT:FUNDS_CLAIMED {{\"account\": \"{}\", \"amount\": \"{}\", \"timestamp\": {}}}",
+            account,
+            amount,
+            env::block_timestamp()
+        ));
+        
+        Promise::new(account.clone()).transfer(amount).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_ON_CLAIM)
+                .on_claim(account, U128(amount)),
+        )
+    }
+
+    /// Restore the claimable balance if the claim transfer failed
+    #[private]
+    pub fn on_claim(&mut self, account: AccountId, amount: U128) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+        
+        let balance = self.claimable.get(&account).unwrap_or(0);
+        self.claimable.insert(&account, &(balance + amount.0));
+        
+        env::log_str(&format!(
+            "EVENT:CLAIM_REFUNDED {{\"account\": \"{}\", \"amount\": \"{}\", \"timestamp\": {}}}",
+            account,
+            amount.0,
+            env::block_timestamp()
+        ));
+        
+        false
+    }
+
     // ------------------------------------------------------------------------
     // QUERY FUNCTIONS
     // ------------------------------------------------------------------------
@@ -327,19 +721,178 @@ impl LoanTradingContract {
         self.loan_tokens.get(&token_id)
     }
 
+    /// Check whether a token ID is well-formed and not yet registered
+    pub fn is_token_id_available(&self, token_id: String) -> bool {
+        !token_id.is_empty() && self.loan_tokens.get(&token_id).is_none()
+    }
+
     /// Get ownership breakdown
     pub fn get_ownership_breakdown(&self, token_id: String) -> Vec<FractionalOwnership> {
         self.loan_tokens
             .get(&token_id)
             .map(|token| token.owners)
-            .unwrap_or_else(|| vec![])
+            .unwrap_or_default()
+    }
+
+    /// Get the originator's retained stake as (basis points, value in yoctoNEAR)
+    pub fn get_originator_retention(&self, token_id: String) -> (u64, Balance) {
+        let loan_token = self.loan_tokens
+            .get(&token_id)
+            .expect("Loan token not found");
+        
+        let retained = loan_token.owners
+            .iter()
+            .find(|o| o.owner == loan_token.originator)
+            .map(|o| o.fraction)
+            .unwrap_or(0);
+        
+        (retained, Self::share_of_bps(loan_token.total_value, retained))
     }
 
     /// Get transfer history
     pub fn get_transfer_history(&self, token_id: String) -> Vec<TransferEvent> {
         self.transfer_history
             .get(&token_id)
-            .unwrap_or_else(|| vec![])
+            .unwrap_or_default()
+    }
+
+    /// Get transfers across all tokens within an inclusive timestamp window, oldest first.
+    /// Scans and sorts every token's history, so this is expensive on large contracts.
+    pub fn get_all_transfers_in_range(
+        &self,
+        from_ts: Timestamp,
+        to_ts: Timestamp,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<TransferEvent> {
+        let mut events: Vec<TransferEvent> = self.transfer_history
+            .values()
+            .flatten()
+            .filter(|event| event.timestamp >= from_ts && event.timestamp <= to_ts)
+            .collect();
+        
+        events.sort_by_key(|event| (event.timestamp, event.block_height));
+        
+        events
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Explain every transfer rule that would apply to a transfer and whether it passes.
+    /// Each entry is prefixed with "PASS" or "FAIL".
+    pub fn explain_transfer(
+        &self,
+        token_id: String,
+        from: AccountId,
+        to: AccountId,
+        fraction: u64,
+    ) -> Vec<String> {
+        // No transfer rule currently depends on the receiver
+        let _ = to;
+        let mut results = Vec::new();
+        
+        let loan_token = match self.loan_tokens.get(&token_id) {
+            Some(token) => token,
+            None => return vec![format!("FAIL token: {} not found", token_id)],
+        };
+        results.push(format!("PASS token: {} exists", token_id));
+        
+        if loan_token.lifecycle_status == LifecycleStatus::Active {
+            results.push("PASS status: loan is Active".to_string());
+        } else {
+            results.push(format!(
+                "FAIL status: loan is {:?}, must be Active",
+                loan_token.lifecycle_status
+            ));
+        }
+        
+        if self.is_owner_position_frozen(token_id.clone(), from.clone()) {
+            results.push("FAIL frozen: sender position is frozen".to_string());
+        } else {
+            results.push("PASS frozen: sender position is not frozen".to_string());
+        }
+        
+        if fraction > 0 && fraction <= 10000 {
+            results.push(format!("PASS fraction: {} bp is valid", fraction));
+        } else {
+            results.push(format!("FAIL fraction: {} bp must be between 1 and 10000", fraction));
+        }
+        
+        let sender_fraction = loan_token.owners
+            .iter()
+            .find(|o| o.owner == from)
+            .map(|o| o.fraction)
+            .unwrap_or(0);
+        if sender_fraction >= fraction && sender_fraction > 0 {
+            results.push(format!("PASS ownership: sender holds {} bp", sender_fraction));
+        } else {
+            results.push(format!(
+                "FAIL ownership: sender holds {} bp, needs {} bp",
+                sender_fraction, fraction
+            ));
+        }
+        
+        match loan_token.lot_size_bps {
+            None => results.push("PASS lot size: no lot size set".to_string()),
+            Some(lot_size) if fraction.is_multiple_of(lot_size) => results.push(format!(
+                "PASS lot size: {} bp is a multiple of {} bp",
+                fraction, lot_size
+            )),
+            Some(_) if fraction == sender_fraction => {
+                results.push("PASS lot size: full position liquidation".to_string())
+            }
+            Some(lot_size) => results.push(format!(
+                "FAIL lot size: {} bp is not a multiple of {} bp",
+                fraction, lot_size
+            )),
+        }
+        
+        let locked_until = self.acquired_at
+            .get(&(token_id, from))
+            .map(|acquired| acquired.saturating_add(self.post_purchase_lock_ns));
+        match locked_until {
+            Some(until) if env::block_timestamp() < until => {
+                results.push(format!("FAIL post-purchase lock: locked until {}", until))
+            }
+            _ => results.push("PASS post-purchase lock: not locked".to_string()),
+        }
+        
+        results
+    }
+
+    /// Get paginated token IDs that have never been transferred since issuance
+    pub fn get_untraded_tokens(&self, from_index: u64, limit: u64) -> Vec<String> {
+        self.loan_tokens
+            .keys()
+            .filter(|token_id| self.get_transfer_history(token_id.clone()).is_empty())
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Get the median per-basis-point price across priced transfers of a token.
+    /// An even count averages the two middle prices; None if the token has no priced trades.
+    pub fn get_median_price(&self, token_id: String) -> Option<Balance> {
+        let mut prices: Vec<Balance> = self.get_transfer_history(token_id)
+            .iter()
+            .filter(|event| event.price > 0)
+            .map(|event| event.price / event.fraction as Balance)
+            .collect();
+        
+        if prices.is_empty() {
+            return None;
+        }
+        
+        prices.sort_unstable();
+        let mid = prices.len() / 2;
+        
+        if prices.len().is_multiple_of(2) {
+            Some((prices[mid - 1] + prices[mid]) / 2)
+        } else {
+            Some(prices[mid])
+        }
     }
 
     /// Get all tokens for an owner
@@ -352,6 +905,100 @@ impl LoanTradingContract {
             .collect()
     }
 
+    /// Get account activity as (sends, receives, total_spent) across all tokens.
+    /// Scans every token's transfer history, so cost is O(total transfer events).
+    pub fn get_account_activity(&self, account: AccountId) -> (u64, u64, Balance) {
+        let mut sends = 0;
+        let mut receives = 0;
+        let mut total_spent: Balance = 0;
+        
+        for history in self.transfer_history.values() {
+            for event in history.iter() {
+                if event.from == account {
+                    sends += 1;
+                }
+                if event.to == account {
+                    receives += 1;
+                    total_spent += event.price;
+                }
+            }
+        }
+        
+        (sends, receives, total_spent)
+    }
+
+    /// Get cumulative distribution totals by type for a token
+    pub fn get_distribution_breakdown(&self, token_id: String) -> DistributionBreakdown {
+        self.distribution_totals
+            .get(&token_id)
+            .unwrap_or_default()
+    }
+
+    /// Preview how a distribution of `amount` would split across current owners,
+    /// including the rounding remainder, without changing state
+    pub fn preview_distribution(&self, token_id: String, amount: Balance) -> Vec<(AccountId, Balance)> {
+        let loan_token = self.loan_tokens
+            .get(&token_id)
+            .expect("Loan token not found");
+        
+        self.compute_pro_rata(&loan_token, amount)
+    }
+
+    /// Get claimable balance for an account
+    pub fn get_claimable(&self, account: AccountId) -> Balance {
+        self.claimable.get(&account).unwrap_or(0)
+    }
+
+    /// Get an originator's Active or Restructured loans, largest total_value first, paginated
+    pub fn get_originator_loans_by_value(
+        &self,
+        originator: AccountId,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<LoanToken> {
+        let mut tokens: Vec<LoanToken> = self.loan_tokens
+            .values()
+            .filter(|token| {
+                token.originator == originator
+                    && matches!(
+                        token.lifecycle_status,
+                        LifecycleStatus::Active | LifecycleStatus::Restructured
+                    )
+            })
+            .collect();
+        
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.total_value));
+        
+        tokens
+            .into_iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Check whether an owner's position in a token is frozen
+    pub fn is_owner_position_frozen(&self, token_id: String, owner: AccountId) -> bool {
+        self.frozen_positions.get(&(token_id, owner)).is_some()
+    }
+
+    /// Get a consolidated summary of an account's holdings and claimable funds.
+    /// Scans every token, so this is a heavy view.
+    pub fn get_account_summary(&self, account: AccountId) -> AccountSummary {
+        let mut summary = AccountSummary {
+            total_claimable: self.get_claimable(account.clone()),
+            ..Default::default()
+        };
+        
+        for token in self.loan_tokens.values() {
+            if let Some(ownership) = token.owners.iter().find(|o| o.owner == account) {
+                summary.holdings_count += 1;
+                summary.portfolio_value += Self::share_of_bps(token.total_value, ownership.fraction);
+            }
+        }
+        
+        summary
+    }
+
     /// Check if account is authorized originator
     pub fn is_authorized_originator(&self, account: AccountId) -> bool {
         self.authorized_originators.get(&account).is_some()
@@ -362,6 +1009,16 @@ impl LoanTradingContract {
         self.version.clone()
     }
 
+    /// Get storage usage as (total_storage_bytes, bytes_per_token_estimate)
+    pub fn get_storage_report(&self) -> (u64, u64) {
+        let total_storage = env::storage_usage();
+        let token_count = self.loan_tokens.len();
+        
+        let per_token = total_storage.checked_div(token_count).unwrap_or(0);
+        
+        (total_storage, per_token)
+    }
+
     // ------------------------------------------------------------------------
     // INTERNAL HELPERS
     // ------------------------------------------------------------------------
@@ -373,17 +1030,48 @@ impl LoanTradingContract {
             "Only admin can perform this action"
         );
     }
-}
 
-// ============================================================================
-// TESTS
+    /// Exact floor(amount * fraction / 10000), split so it cannot overflow for any amount
+    fn share_of_bps(amount: Balance, fraction: u64) -> Balance {
+        let fraction = fraction as Balance;
+        amount / 10000 * fraction + amount % 10000 * fraction / 10000
+    }
+
+    /// Split an amount pro-rata by ownership fraction.
+    /// Any rounding remainder goes to the largest holder (first listed on ties).
+    fn compute_pro_rata(&self, loan_token: &LoanToken, amount: Balance) -> Vec<(AccountId, Balance)> {
+        let mut shares: Vec<(AccountId, Balance)> = loan_token.owners
+            .iter()
+            .map(|o| (o.owner.clone(), Self::share_of_bps(amount, o.fraction)))
+            .collect();
+        
+        let allocated: Balance = shares.iter().map(|(_, share)| share).sum();
+        let remainder = amount - allocated;
+        
+        if remainder > 0 {
+            let largest_index = loan_token.owners
+                .iter()
+                .enumerate()
+                .max_by(|(i, a), (j, b)| a.fraction.cmp(&b.fraction).then(j.cmp(i)))
+                .map(|(i, _)| i)
+                .expect("Loan token has no owners");
+            shares[largest_index].1 += remainder;
+        }
+        
+        shares
+    }
+}
+
+// ============================================================================
+// TESTS
 // ============================================================================
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::testing_env;
+    use near_sdk::test_utils::{accounts, get_created_receipts, VMContextBuilder};
+    use near_sdk::mock::VmAction;
+    use near_sdk::{testing_env, PromiseResult, RuntimeFeesConfig, VMConfig};
 
     fn get_context(predecessor: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -398,12 +1086,12 @@ mod tests {
         
         let contract = LoanTradingContract::new(accounts(0));
         assert_eq!(contract.admin, accounts(0));
-        assert_eq!(contract.version, "1.0.0");
+        assert_eq!(contract.version, "1.1.0");
     }
 
     #[test]
     fn test_authorize_originator() {
-        let mut context = get_context(accounts(0));
+        let context = get_context(accounts(0));
         testing_env!(context.build());
         
         let mut contract = LoanTradingContract::new(accounts(0));
@@ -468,4 +1156,1047 @@ mod tests {
         assert_eq!(owner1.fraction, 7500); // 75%
         assert_eq!(owner2.fraction, 2500); // 25%
     }
+
+    /// Contract with accounts(1) authorized and LOAN-001 registered by it.
+    /// Leaves accounts(1) as the predecessor.
+    fn setup_contract(context: &mut VMContextBuilder) -> LoanTradingContract {
+        testing_env!(context.build());
+        
+        let mut contract = LoanTradingContract::new(accounts(0));
+        contract.authorize_originator(accounts(1));
+        
+        set_caller(context, accounts(1));
+        contract.register_loan_token(
+            "LOAN-001".to_string(),
+            "OFF-CHAIN-001".to_string(),
+            1000000,
+        );
+        
+        contract
+    }
+
+    /// LOAN-001 held 65% / 25% / 10% by accounts(1), accounts(2) and accounts(3)
+    fn setup_multi_owner_token(context: &mut VMContextBuilder) -> LoanTradingContract {
+        let mut contract = setup_contract(context);
+        
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            100000,
+        );
+        
+        contract
+    }
+
+    /// Parse the JSON body of the most recent BONUS_DISTRIBUTED log
+    fn bonus_event() -> near_sdk::serde_json::Value {
+        let logs = near_sdk::test_utils::get_logs();
+        let log = logs
+            .iter()
+            .rev()
+            .find_map(|l| l.strip_prefix("EVENT:BONUS_DISTRIBUTED "))
+            .expect("No BONUS_DISTRIBUTED log");
+        near_sdk::serde_json::from_str(log).expect("BONUS_DISTRIBUTED log is not valid JSON")
+    }
+
+    fn set_caller(context: &mut VMContextBuilder, caller: AccountId) {
+        context.predecessor_account_id(caller);
+        testing_env!(context.build());
+    }
+
+    fn set_timestamp(context: &mut VMContextBuilder, timestamp: Timestamp) {
+        context.block_timestamp(timestamp);
+        testing_env!(context.build());
+    }
+
+    #[test]
+    fn test_get_storage_report() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        let (initial_storage, initial_per_token) = contract.get_storage_report();
+        assert_eq!(initial_per_token, initial_storage);
+        
+        for i in 2..4 {
+            contract.register_loan_token(
+                format!("LOAN-00{}", i),
+                format!("OFF-CHAIN-00{}", i),
+                1000000,
+            );
+        }
+        
+        let (total_storage, per_token) = contract.get_storage_report();
+        assert!(total_storage > initial_storage);
+        assert!(per_token > 0);
+        assert!(per_token <= total_storage);
+        assert_eq!(per_token, total_storage / 3);
+    }
+
+    #[test]
+    fn test_get_account_activity() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        contract.register_loan_token(
+            "LOAN-002".to_string(),
+            "OFF-CHAIN-002".to_string(),
+            2000000,
+        );
+        
+        // accounts(2) buys on two different tokens
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        contract.transfer_fractional_ownership(
+            "LOAN-002".to_string(),
+            accounts(2),
+            1000,
+            200000,
+        );
+        
+        // accounts(2) sells once
+        set_caller(&mut context, accounts(2));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            500,
+            60000,
+        );
+        
+        let (sends, receives, total_spent) = contract.get_account_activity(accounts(2));
+        assert_eq!(sends, 1);
+        assert_eq!(receives, 2);
+        assert_eq!(total_spent, 450000);
+        
+        let (sends, receives, total_spent) = contract.get_account_activity(accounts(4));
+        assert_eq!((sends, receives, total_spent), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_lot_size_enforcement() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        // Odd 3 bp residual created before the lot size applies
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            3,
+            300,
+        );
+        contract.set_lot_size("LOAN-001".to_string(), Some(100));
+        
+        // Multiple of the lot size is allowed
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            200,
+            20000,
+        );
+        
+        // Full liquidation of a 3 bp residual is allowed
+        set_caller(&mut context, accounts(2));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            3,
+            300,
+        );
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        assert!(token.owners.iter().all(|o| o.owner != accounts(2)));
+        let owner3 = token.owners.iter().find(|o| o.owner == accounts(3)).unwrap();
+        assert_eq!(owner3.fraction, 203);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fraction must be a multiple of the lot size")]
+    fn test_lot_size_rejects_partial_lot() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        contract.set_lot_size("LOAN-001".to_string(), Some(100));
+        
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            150,
+            15000,
+        );
+    }
+
+    #[test]
+    fn test_is_token_id_available() {
+        let mut context = get_context(accounts(0));
+        let contract = setup_contract(&mut context);
+        
+        assert!(contract.is_token_id_available("LOAN-002".to_string()));
+        assert!(!contract.is_token_id_available("LOAN-001".to_string()));
+        assert!(!contract.is_token_id_available("".to_string()));
+    }
+
+    #[test]
+    fn test_distribute_bonus() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        context.attached_deposit(1001);
+        set_caller(&mut context, accounts(0));
+        
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        // 65% / 25% / 10%, with the 1 yocto remainder to the largest holder
+        assert_eq!(contract.get_claimable(accounts(1)), 651);
+        assert_eq!(contract.get_claimable(accounts(2)), 250);
+        assert_eq!(contract.get_claimable(accounts(3)), 100);
+        
+        let event = bonus_event();
+        assert_eq!(event["distribution_type"], "bonus");
+        assert_eq!(event["reason"], "Launch promotion");
+    }
+
+    #[test]
+    fn test_distribute_bonus_escapes_reason() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        context.attached_deposit(1000);
+        testing_env!(context.build());
+        
+        let reason = "Promo\", \"amount\": \"1";
+        contract.distribute_bonus("LOAN-001".to_string(), reason.to_string());
+        
+        let event = bonus_event();
+        assert_eq!(event["reason"], reason);
+        assert_eq!(event["amount"], "1000");
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin or originator can distribute a bonus")]
+    fn test_distribute_bonus_unauthorized() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        context.attached_deposit(1000);
+        set_caller(&mut context, accounts(2));
+        
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+    }
+
+    #[test]
+    fn test_claim() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        context.attached_deposit(1000);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        context.attached_deposit(0);
+        set_caller(&mut context, accounts(2));
+        contract.claim();
+        
+        assert_eq!(contract.get_claimable(accounts(2)), 0);
+        assert_eq!(contract.get_claimable(accounts(1)), 650);
+        
+        let receipts = get_created_receipts();
+        assert_eq!(receipts[0].receiver_id, accounts(2));
+        assert_eq!(receipts[0].actions, vec![VmAction::Transfer { deposit: 250 }]);
+        assert!(matches!(
+            &receipts[1].actions[0],
+            VmAction::FunctionCall { function_name, .. } if function_name == "on_claim"
+        ));
+    }
+
+    #[test]
+    fn test_get_distribution_breakdown() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        assert_eq!(
+            contract.get_distribution_breakdown("LOAN-001".to_string()),
+            DistributionBreakdown::default()
+        );
+        
+        context.attached_deposit(1000);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        context.attached_deposit(500);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Loyalty rebate".to_string());
+        
+        let breakdown = contract.get_distribution_breakdown("LOAN-001".to_string());
+        assert_eq!(breakdown.bonus_total, 1500);
+        assert_eq!(breakdown.interest_total, 0);
+        assert_eq!(breakdown.recovery_total, 0);
+        assert_eq!(breakdown.principal_total, 0);
+        
+        let total = breakdown.interest_total
+            + breakdown.recovery_total
+            + breakdown.bonus_total
+            + breakdown.principal_total;
+        let claimable: Balance = (1..4).map(|i| contract.get_claimable(accounts(i))).sum();
+        assert_eq!(total, claimable);
+    }
+
+    #[test]
+    fn test_post_purchase_lock_elapsed() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(1000);
+        
+        // accounts(2) buys 25% at t=5000
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        
+        set_caller(&mut context, accounts(2));
+        set_timestamp(&mut context, 6000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            110000,
+        );
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        let owner2 = token.owners.iter().find(|o| o.owner == accounts(2)).unwrap();
+        assert_eq!(owner2.fraction, 1500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Position is locked after purchase")]
+    fn test_post_purchase_lock_too_soon() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(1000);
+        
+        // accounts(2) buys 25% at t=5000
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        
+        set_caller(&mut context, accounts(2));
+        set_timestamp(&mut context, 5999);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            110000,
+        );
+    }
+
+    #[test]
+    fn test_explain_transfer_all_pass() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(1000);
+        
+        // accounts(2) buys 25% at t=5000
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        
+        let results = contract.explain_transfer(
+            "LOAN-001".to_string(),
+            accounts(1),
+            accounts(3),
+            1000,
+        );
+        
+        assert_eq!(results.len(), 7);
+        assert!(results.iter().all(|r| r.starts_with("PASS")));
+    }
+
+    #[test]
+    fn test_explain_transfer_multiple_failures() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(1000);
+        
+        // accounts(2) buys 25% at t=5000
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        contract.set_lot_size("LOAN-001".to_string(), Some(100));
+        
+        // accounts(2) is still within the hold period and 150 bp is off-lot
+        let results = contract.explain_transfer(
+            "LOAN-001".to_string(),
+            accounts(2),
+            accounts(3),
+            150,
+        );
+        
+        let failures: Vec<&String> = results.iter().filter(|r| r.starts_with("FAIL")).collect();
+        assert_eq!(failures.len(), 2);
+        assert!(failures.iter().any(|r| r.starts_with("FAIL lot size")));
+        assert!(failures.iter().any(|r| r.starts_with("FAIL post-purchase lock")));
+    }
+
+    #[test]
+    fn test_get_all_transfers_in_range() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        contract.register_loan_token(
+            "LOAN-002".to_string(),
+            "OFF-CHAIN-002".to_string(),
+            2000000,
+        );
+        
+        // (timestamp, token, fraction) interleaved across both tokens
+        let transfers = [
+            (100, "LOAN-001", 100),
+            (200, "LOAN-002", 200),
+            (300, "LOAN-001", 300),
+            (400, "LOAN-002", 400),
+            (500, "LOAN-001", 500),
+        ];
+        for (timestamp, token_id, fraction) in transfers {
+            set_timestamp(&mut context, timestamp);
+            contract.transfer_fractional_ownership(
+                token_id.to_string(),
+                accounts(2),
+                fraction,
+                0,
+            );
+        }
+        
+        let events = contract.get_all_transfers_in_range(200, 400, 0, 10);
+        let fractions: Vec<u64> = events.iter().map(|e| e.fraction).collect();
+        assert_eq!(fractions, vec![200, 300, 400]);
+        assert_eq!(events[0].token_id, "LOAN-002");
+        assert_eq!(events[1].token_id, "LOAN-001");
+        
+        let page = contract.get_all_transfers_in_range(200, 400, 1, 1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].timestamp, 300);
+        
+        assert!(contract.get_all_transfers_in_range(600, 700, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_get_originator_retention() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            9000,
+            900000,
+        );
+        
+        assert_eq!(
+            contract.get_originator_retention("LOAN-001".to_string()),
+            (1000, 100000)
+        );
+        
+        // Fully divest the remaining 10%
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            100000,
+        );
+        
+        assert_eq!(contract.get_originator_retention("LOAN-001".to_string()), (0, 0));
+    }
+
+    #[test]
+    fn test_freeze_owner_position() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.freeze_owner_position("LOAN-001".to_string(), accounts(2));
+        assert!(contract.is_owner_position_frozen("LOAN-001".to_string(), accounts(2)));
+        
+        // Another owner of the same token can still trade
+        set_caller(&mut context, accounts(3));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(4),
+            500,
+            50000,
+        );
+        
+        // Unfreezing restores the frozen owner
+        set_caller(&mut context, accounts(0));
+        contract.unfreeze_owner_position("LOAN-001".to_string(), accounts(2));
+        
+        set_caller(&mut context, accounts(2));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(4),
+            500,
+            50000,
+        );
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        let owner4 = token.owners.iter().find(|o| o.owner == accounts(4)).unwrap();
+        assert_eq!(owner4.fraction, 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner position is frozen")]
+    fn test_frozen_owner_cannot_sell() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.freeze_owner_position("LOAN-001".to_string(), accounts(2));
+        
+        set_caller(&mut context, accounts(2));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(4),
+            500,
+            50000,
+        );
+    }
+
+    #[test]
+    fn test_get_untraded_tokens() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        for i in 2..=4 {
+            contract.register_loan_token(
+                format!("LOAN-00{}", i),
+                format!("OFF-CHAIN-00{}", i),
+                1000000,
+            );
+        }
+        for token_id in ["LOAN-001", "LOAN-003"] {
+            contract.transfer_fractional_ownership(
+                token_id.to_string(),
+                accounts(2),
+                1000,
+                100000,
+            );
+        }
+        
+        assert_eq!(
+            contract.get_untraded_tokens(0, 10),
+            vec!["LOAN-002".to_string(), "LOAN-004".to_string()]
+        );
+        assert_eq!(contract.get_untraded_tokens(1, 10), vec!["LOAN-004".to_string()]);
+        assert_eq!(contract.get_untraded_tokens(0, 1), vec!["LOAN-002".to_string()]);
+    }
+
+    #[test]
+    fn test_rescue_ft() {
+        let mut context = get_context(accounts(0));
+        context.attached_deposit(1);
+        testing_env!(context.build());
+        
+        let mut contract = LoanTradingContract::new(accounts(0));
+        contract.rescue_ft(accounts(4), accounts(2), U128(500));
+        
+        // Nothing is logged until the transfer outcome is known
+        assert!(near_sdk::test_utils::get_logs().is_empty());
+        
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, accounts(4));
+        assert!(matches!(
+            &receipts[1].actions[0],
+            VmAction::FunctionCall { function_name, .. } if function_name == "on_rescue_ft"
+        ));
+        
+        match &receipts[0].actions[0] {
+            VmAction::FunctionCall { function_name, args, gas, deposit } => {
+                assert_eq!(function_name, "ft_transfer");
+                assert_eq!(*deposit, 1);
+                assert_eq!(*gas, GAS_FOR_FT_TRANSFER);
+                
+                let args: near_sdk::serde_json::Value = near_sdk::serde_json::from_slice(args).unwrap();
+                assert_eq!(args["receiver_id"], accounts(2).to_string());
+                assert_eq!(args["amount"], "500");
+            }
+            action => panic!("Unexpected action: {:?}", action),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin can perform this action")]
+    fn test_rescue_ft_unauthorized() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        context.attached_deposit(1);
+        testing_env!(context.build());
+        contract.rescue_ft(accounts(4), accounts(1), U128(500));
+    }
+
+    #[test]
+    fn test_preview_distribution_matches_distribution() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        let preview = contract.preview_distribution("LOAN-001".to_string(), 999999);
+        assert_eq!(
+            preview,
+            vec![
+                (accounts(1), 650001),
+                (accounts(2), 249999),
+                (accounts(3), 99999),
+            ]
+        );
+        assert_eq!(contract.get_claimable(accounts(1)), 0);
+        
+        context.attached_deposit(999999);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        for (owner, share) in preview {
+            assert_eq!(contract.get_claimable(owner), share);
+        }
+    }
+
+    #[test]
+    fn test_get_median_price() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        assert_eq!(contract.get_median_price("LOAN-001".to_string()), None);
+        
+        // Per-bp prices 10, 30, 20, plus an unpriced transfer that is ignored
+        for (fraction, price) in [(100, 1000), (200, 6000), (50, 1000), (300, 0)] {
+            contract.transfer_fractional_ownership(
+                "LOAN-001".to_string(),
+                accounts(2),
+                fraction,
+                price,
+            );
+        }
+        assert_eq!(contract.get_median_price("LOAN-001".to_string()), Some(20));
+        
+        // Per-bp price 50 makes the count even: (20 + 30) / 2
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            100,
+            5000,
+        );
+        assert_eq!(contract.get_median_price("LOAN-001".to_string()), Some(25));
+    }
+
+    #[test]
+    fn test_get_originator_loans_by_value() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        for (token_id, total_value) in [
+            ("LOAN-002", 3000000),
+            ("LOAN-003", 2000000),
+            ("LOAN-004", 9000000),
+        ] {
+            contract.register_loan_token(
+                token_id.to_string(),
+                format!("OFF-{}", token_id),
+                total_value,
+            );
+        }
+        contract.update_lifecycle_status("LOAN-004".to_string(), LifecycleStatus::Settled);
+        
+        // Another originator's loan is excluded
+        set_caller(&mut context, accounts(0));
+        contract.authorize_originator(accounts(2));
+        set_caller(&mut context, accounts(2));
+        contract.register_loan_token(
+            "LOAN-005".to_string(),
+            "OFF-LOAN-005".to_string(),
+            5000000,
+        );
+        
+        let ids = |tokens: Vec<LoanToken>| -> Vec<String> {
+            tokens.into_iter().map(|t| t.token_id).collect()
+        };
+        
+        assert_eq!(
+            ids(contract.get_originator_loans_by_value(accounts(1), 0, 10)),
+            vec!["LOAN-002", "LOAN-003", "LOAN-001"]
+        );
+        assert_eq!(
+            ids(contract.get_originator_loans_by_value(accounts(1), 1, 1)),
+            vec!["LOAN-003"]
+        );
+        assert!(contract.get_originator_loans_by_value(accounts(1), 3, 10).is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_change_after_cooldown() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_lifecycle_change_cooldown(1000);
+        
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 6000);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Restructured);
+        
+        set_timestamp(&mut context, 7000);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Active);
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        assert_eq!(token.lifecycle_status, LifecycleStatus::Active);
+    }
+
+    #[test]
+    #[should_panic(expected = "Lifecycle status changed too recently")]
+    fn test_lifecycle_change_too_soon() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_lifecycle_change_cooldown(1000);
+        
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 6000);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Restructured);
+        
+        set_timestamp(&mut context, 6999);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Active);
+    }
+
+    #[test]
+    fn test_get_account_summary() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        contract.register_loan_token(
+            "LOAN-002".to_string(),
+            "OFF-CHAIN-002".to_string(),
+            2000000,
+        );
+        contract.transfer_fractional_ownership(
+            "LOAN-002".to_string(),
+            accounts(2),
+            5000,
+            1000000,
+        );
+        
+        context.attached_deposit(1000);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        assert_eq!(
+            contract.get_account_summary(accounts(2)),
+            AccountSummary {
+                holdings_count: 2,
+                portfolio_value: 250000 + 1000000,
+                total_claimable: 250,
+            }
+        );
+        
+        assert_eq!(
+            contract.get_account_summary(accounts(5)),
+            AccountSummary::default()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Position is locked after purchase")]
+    fn test_post_purchase_lock_started_by_zero_price_receipt() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(1000);
+        
+        // accounts(2) receives 25% with no recorded price at t=5000
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            0,
+        );
+        
+        // Reselling straight away is still blocked
+        set_caller(&mut context, accounts(2));
+        set_timestamp(&mut context, 5500);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            0,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Position is locked after purchase")]
+    fn test_post_purchase_lock_saturates() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(u64::MAX);
+        
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        
+        set_caller(&mut context, accounts(2));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(3),
+            1000,
+            100000,
+        );
+    }
+
+    #[test]
+    fn test_explain_transfer_saturates_lock() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_post_purchase_lock(u64::MAX);
+        
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 5000);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        
+        let results = contract.explain_transfer(
+            "LOAN-001".to_string(),
+            accounts(2),
+            accounts(3),
+            1000,
+        );
+        assert!(results.contains(&format!("FAIL post-purchase lock: locked until {}", u64::MAX)));
+    }
+
+    #[test]
+    fn test_lifecycle_cooldown_ignores_transfers() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            1,
+            100,
+        );
+        
+        set_caller(&mut context, accounts(0));
+        contract.set_lifecycle_change_cooldown(1000);
+        
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 6000);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Active);
+        
+        // A 1 bp self-transfer inside the window
+        set_caller(&mut context, accounts(2));
+        set_timestamp(&mut context, 6500);
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            1,
+            0,
+        );
+        
+        // The status change is still due once the cooldown since the last change elapses
+        set_caller(&mut context, accounts(1));
+        set_timestamp(&mut context, 7000);
+        contract.update_lifecycle_status("LOAN-001".to_string(), LifecycleStatus::Defaulted);
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        assert_eq!(token.lifecycle_status, LifecycleStatus::Defaulted);
+    }
+
+    #[test]
+    fn test_on_claim_restores_failed_transfer() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_multi_owner_token(&mut context);
+        
+        context.attached_deposit(1000);
+        testing_env!(context.build());
+        contract.distribute_bonus("LOAN-001".to_string(), "Launch promotion".to_string());
+        
+        context.attached_deposit(0);
+        set_caller(&mut context, accounts(2));
+        contract.claim();
+        assert_eq!(contract.get_claimable(accounts(2)), 0);
+        
+        // Callback from the contract itself after the transfer bounced
+        context.predecessor_account_id(accounts(0));
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.on_claim(accounts(2), U128(250)));
+        assert_eq!(contract.get_claimable(accounts(2)), 250);
+    }
+
+    #[test]
+    fn test_on_claim_keeps_successful_transfer() {
+        let mut context = get_context(accounts(0));
+        let mut contract = setup_contract(&mut context);
+        
+        context.predecessor_account_id(accounts(0));
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        assert!(contract.on_claim(accounts(2), U128(250)));
+        assert_eq!(contract.get_claimable(accounts(2)), 0);
+    }
+
+    #[test]
+    fn test_preview_distribution_large_amount() {
+        let mut context = get_context(accounts(0));
+        let contract = setup_multi_owner_token(&mut context);
+        
+        let preview = contract.preview_distribution("LOAN-001".to_string(), u128::MAX);
+        let total: Balance = preview.iter().map(|(_, share)| share).sum();
+        assert_eq!(total, u128::MAX);
+        assert_eq!(preview[2], (accounts(3), u128::MAX / 10));
+    }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        
+        let mut old = LoanTradingContractV1 {
+            loan_tokens: UnorderedMap::new(b"l"),
+            transfer_history: UnorderedMap::new(b"t"),
+            authorized_originators: LookupMap::new(b"o"),
+            admin: accounts(0),
+            version: "1.0.0".to_string(),
+        };
+        old.authorized_originators.insert(&accounts(1), &true);
+        old.loan_tokens.insert(&"LOAN-001".to_string(), &LoanTokenV1 {
+            token_id: "LOAN-001".to_string(),
+            off_chain_loan_id: "OFF-CHAIN-001".to_string(),
+            total_value: 1000000,
+            owners: vec![FractionalOwnership {
+                owner: accounts(1),
+                fraction: 10000,
+            }],
+            lifecycle_status: LifecycleStatus::Active,
+            created_at: 0,
+            updated_at: 0,
+            originator: accounts(1),
+        });
+        old.transfer_history.insert(&"LOAN-001".to_string(), &vec![]);
+        env::state_write(&old);
+        
+        let mut contract = LoanTradingContract::migrate();
+        assert_eq!(contract.get_version(), "1.1.0");
+        assert_eq!(contract.admin, accounts(0));
+        assert!(contract.is_authorized_originator(accounts(1)));
+        
+        let token = contract.get_loan_token("LOAN-001".to_string()).unwrap();
+        assert_eq!(token.lot_size_bps, None);
+        assert_eq!(token.owners[0].fraction, 10000);
+        
+        // Migrated tokens keep trading
+        set_caller(&mut context, accounts(1));
+        contract.transfer_fractional_ownership(
+            "LOAN-001".to_string(),
+            accounts(2),
+            2500,
+            250000,
+        );
+        assert_eq!(contract.get_transfer_history("LOAN-001".to_string()).len(), 1);
+    }
+
+    #[test]
+    fn test_on_rescue_ft_logs_success() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = LoanTradingContract::new(accounts(0));
+        
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        assert!(contract.on_rescue_ft(accounts(4), accounts(2), U128(500), accounts(0)));
+        
+        let logs = near_sdk::test_utils::get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].starts_with("EVENT:FT_RESCUED "));
+    }
+
+    #[test]
+    fn test_on_rescue_ft_failed_transfer() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = LoanTradingContract::new(accounts(0));
+        
+        testing_env!(
+            context.build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.on_rescue_ft(accounts(4), accounts(2), U128(500), accounts(0)));
+        
+        let logs = near_sdk::test_utils::get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].starts_with("EVENT:FT_RESCUE_FAILED "));
+    }
 }